@@ -13,11 +13,11 @@ use {
         DeviceAddress, MAX_PAYLOAD_SIZE,
     },
     crate::ble::{
-        bytes::{ByteWriter, ToBytes},
+        bytes::{ByteReader, ByteWriter, FromBytes, ToBytes},
         Error,
     },
     byteorder::{ByteOrder, LittleEndian},
-    core::{fmt, iter},
+    core::{fmt, iter, time::Duration},
 };
 
 /// Stores an advertising channel PDU.
@@ -55,37 +55,16 @@ impl PduBuf {
         })
     }
 
-    /// Creates a connectable undirected advertising PDU (`ADV_IND`).
-    ///
-    /// # Parameters
-    ///
-    /// * `adv`: The advertiser address, the address of the device sending this
-    ///   PDU.
-    /// * `adv_data`: Additional advertising data to send.
-    pub fn connectable_undirected(
-        advertiser_addr: DeviceAddress,
-        advertiser_data: &[AdStructure],
-    ) -> Result<Self, Error> {
-        Self::adv(
-            PduType::AdvInd,
-            advertiser_addr,
-            &mut advertiser_data.iter(),
-        )
-    }
-
-    /// Creates a connectable directed advertising PDU (`ADV_DIRECT_IND`).
-    pub fn connectable_directed(
-        advertiser_addr: DeviceAddress,
-        initiator_addr: DeviceAddress,
-    ) -> Self {
+    /// Builds a directed advertising PDU (`ADV_DIRECT_IND`) for `target`.
+    fn directed(advertiser_addr: DeviceAddress, target: DeviceAddress) -> Self {
         let mut payload = [0; 37];
         payload[0..6].copy_from_slice(advertiser_addr.raw());
-        payload[6..12].copy_from_slice(initiator_addr.raw());
+        payload[6..12].copy_from_slice(target.raw());
 
         let mut header = Header::new(PduType::AdvDirectInd);
         header.set_payload_length(6 + 6);
         header.set_tx_add(advertiser_addr.is_random());
-        header.set_rx_add(initiator_addr.is_random());
+        header.set_rx_add(target.is_random());
 
         Self {
             header,
@@ -93,42 +72,30 @@ impl PduBuf {
         }
     }
 
-    /// Creates a non-connectable undirected advertising PDU
-    /// (`ADV_NONCONN_IND`).
+    /// Builds the advertising PDU that `addr` would send to transmit `adv`.
     ///
-    /// This is equivalent to `PduBuf::beacon`, which should be preferred when
-    /// building a beacon PDU to improve clarity.
-    pub fn nonconnectable_undirected(
-        advertiser_addr: DeviceAddress,
-        advertiser_data: &[AdStructure],
-    ) -> Result<Self, Error> {
-        Self::adv(
-            PduType::AdvNonconnInd,
-            advertiser_addr,
-            &mut advertiser_data.iter(),
-        )
-    }
-
-    /// Creates a scannable undirected advertising PDU (`ADV_SCAN_IND`).
+    /// This is the single entry point for building any advertising PDU: pass a
+    /// [`ConnectableAdvertisement`] or [`NonconnectableAdvertisement`] and it is mapped to the
+    /// right `PduType`, `TxAdd`/`RxAdd` and payload. Because each variant only carries the fields
+    /// that are legal for the PDU it describes, illegal combinations (such as attaching
+    /// advertising data to a directed PDU, which has a fixed `AdvA`/`TargetA` layout) cannot be
+    /// expressed in the first place.
     ///
-    /// Note that scanning is not supported at the moment.
-    pub fn scannable_undirected(
-        advertiser_addr: DeviceAddress,
-        advertiser_data: &[AdStructure],
-    ) -> Result<Self, Error> {
-        Self::adv(
-            PduType::AdvScanInd,
-            advertiser_addr,
-            &mut advertiser_data.iter(),
-        )
+    /// [`ConnectableAdvertisement`]: enum.ConnectableAdvertisement.html
+    /// [`NonconnectableAdvertisement`]: enum.NonconnectableAdvertisement.html
+    pub fn from_advertisement<A: Advertisement>(addr: DeviceAddress, adv: &A) -> Result<Self, Error> {
+        adv.into_pdu(addr)
     }
 
     /// Creates an advertising channel PDU suitable for building a simple
     /// beacon.
     ///
-    /// This is mostly equivalent to `PduBuf::nonconnectable_undirected`, but it
-    /// will automatically add a suitable `Flags` AD structure to the
-    /// advertising data (this flags is mandatory).
+    /// This is mostly equivalent to passing
+    /// `NonconnectableAdvertisement::NonscannableUndirected` to
+    /// [`PduBuf::from_advertisement`], but it will automatically add a suitable `Flags` AD
+    /// structure to the advertising data (this flags is mandatory).
+    ///
+    /// [`PduBuf::from_advertisement`]: struct.PduBuf.html#method.from_advertisement
     pub fn beacon(
         advertiser_addr: DeviceAddress,
         advertiser_data: &[AdStructure],
@@ -145,12 +112,15 @@ impl PduBuf {
     ///
     /// This should be used when this device would like to initiate pairing.
     ///
-    /// This function is mostly equivalent to `PduBuf::connectable_undirected`,
-    /// but will automatically add a suitable `Flags` AD structure to the
-    /// advertising data.
+    /// This function is mostly equivalent to passing
+    /// `ConnectableAdvertisement::ScannableUndirected` (with empty `scan_data`) to
+    /// [`PduBuf::from_advertisement`], but it will automatically add a suitable `Flags` AD
+    /// structure to the advertising data.
     ///
     /// To establish a connection with an already paired device, a "directed"
     /// advertisement must be sent instead.
+    ///
+    /// [`PduBuf::from_advertisement`]: struct.PduBuf.html#method.from_advertisement
     pub fn discoverable(
         advertiser_addr: DeviceAddress,
         advertiser_data: &[AdStructure],
@@ -163,9 +133,7 @@ impl PduBuf {
         )
     }
 
-    /// Creates a scan request PDU.
-    ///
-    /// Note that scanning is not yet implemented.
+    /// Creates a scan request PDU (`SCAN_REQ`).
     ///
     /// # Parameters
     ///
@@ -173,15 +141,59 @@ impl PduBuf {
     ///   the request).
     /// * `adv`: Device address of the advertising device that this scan request
     ///   is directed towards.
-    pub fn scan_request(_scanner: DeviceAddress, _adv: DeviceAddress) -> Result<Self, Error> {
-        unimplemented!()
+    pub fn scan_request(scanner: DeviceAddress, adv: DeviceAddress) -> Result<Self, Error> {
+        let mut payload = [0; MAX_PAYLOAD_SIZE];
+        payload[0..6].copy_from_slice(scanner.raw());
+        payload[6..12].copy_from_slice(adv.raw());
+
+        let mut header = Header::new(PduType::ScanReq);
+        header.set_payload_length(6 + 6);
+        header.set_tx_add(scanner.is_random());
+        header.set_rx_add(adv.is_random());
+
+        Ok(Self {
+            header,
+            payload_buf: payload,
+        })
     }
 
-    /// Creates a scan response PDU.
+    /// Creates a scan response PDU (`SCAN_RSP`), carrying additional advertising data for a
+    /// scanner that asked for it via a preceding `SCAN_REQ`.
+    ///
+    /// # Parameters
     ///
-    /// Note that scanning is not yet implemented.
-    pub fn scan_response(_adv: DeviceAddress, _scan_data: &[AdStructure]) -> Result<Self, Error> {
-        unimplemented!()
+    /// * `adv`: Device address of the advertising device sending this response.
+    /// * `scan_data`: Additional advertising data to send.
+    pub fn scan_response(adv: DeviceAddress, scan_data: &[AdStructure]) -> Result<Self, Error> {
+        Self::adv(PduType::ScanRsp, adv, &mut scan_data.iter())
+    }
+
+    /// Creates a connection request PDU (`CONNECT_REQ`).
+    ///
+    /// # Parameters
+    ///
+    /// * `initiator`: Device address of the device in Initiating State (sender of the request).
+    /// * `advertiser`: Device address of the advertising device this request is directed at.
+    /// * `ll_data`: Connection parameters the new connection will be established with.
+    pub fn connect_request(
+        initiator: DeviceAddress,
+        advertiser: DeviceAddress,
+        ll_data: &LlData,
+    ) -> Result<Self, Error> {
+        let mut payload = [0; MAX_PAYLOAD_SIZE];
+        payload[0..6].copy_from_slice(initiator.raw());
+        payload[6..12].copy_from_slice(advertiser.raw());
+        ll_data.to_bytes(&mut payload[12..12 + 22]);
+
+        let mut header = Header::new(PduType::ConnectReq);
+        header.set_payload_length(6 + 6 + 22);
+        header.set_tx_add(initiator.is_random());
+        header.set_rx_add(advertiser.is_random());
+
+        Ok(Self {
+            header,
+            payload_buf: payload,
+        })
     }
 
     pub fn header(&self) -> Header {
@@ -192,11 +204,775 @@ impl PduBuf {
         let len = self.header.payload_length() as usize;
         &self.payload_buf[..len]
     }
-}
+}
+
+impl fmt::Debug for PduBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?}, {:?})", self.header(), self.payload())
+    }
+}
+
+/// A connectable advertisement, to be passed to [`PduBuf::from_advertisement`].
+///
+/// Each variant carries exactly the fields that are legal for the PDU it describes.
+///
+/// [`PduBuf::from_advertisement`]: struct.PduBuf.html#method.from_advertisement
+pub enum ConnectableAdvertisement<'a> {
+    /// Connectable, scannable, undirected advertising event (`ADV_IND`).
+    ScannableUndirected {
+        /// Advertising data to send along with the `ADV_IND`.
+        adv_data: &'a [AdStructure<'a>],
+        /// Scan response data to send if a `SCAN_REQ` is received in reply.
+        scan_data: &'a [AdStructure<'a>],
+    },
+    /// Connectable directed advertising event (`ADV_DIRECT_IND`).
+    ///
+    /// Directed advertising has a low and a high duty cycle mode (the latter re-transmitting every
+    /// 3.75 ms and stopping after at most 1.28 s). Both are the same PDU on air and differ only in
+    /// the advertiser's own transmission schedule, which is set by the
+    /// [`AdvInterval`]/[`AdvDuration`] in the [`AdvParams`] passed to [`Advertiser::new`]. Only
+    /// the low duty cycle mode can be scheduled this way: [`AdvInterval`] does not accept
+    /// intervals below 20 ms, so the 3.75 ms high duty cycle interval cannot be expressed.
+    ///
+    /// [`AdvInterval`]: struct.AdvInterval.html
+    /// [`AdvDuration`]: struct.AdvDuration.html
+    /// [`AdvParams`]: struct.AdvParams.html
+    /// [`Advertiser::new`]: struct.Advertiser.html#method.new
+    NonscannableDirected {
+        /// Address of the device this advertisement is directed at.
+        target: DeviceAddress,
+    },
+}
+
+impl<'a> ConnectableAdvertisement<'a> {
+    /// Returns the scan response data to send if this advertisement is scanned, if any.
+    pub fn scan_data(&self) -> Option<&'a [AdStructure<'a>]> {
+        match *self {
+            ConnectableAdvertisement::ScannableUndirected { scan_data, .. } => Some(scan_data),
+            ConnectableAdvertisement::NonscannableDirected { .. } => None,
+        }
+    }
+}
+
+impl<'a> Advertisement for ConnectableAdvertisement<'a> {
+    fn into_pdu(&self, addr: DeviceAddress) -> Result<PduBuf, Error> {
+        match self {
+            ConnectableAdvertisement::ScannableUndirected { adv_data, .. } => {
+                PduBuf::adv(PduType::AdvInd, addr, &mut adv_data.iter())
+            }
+            ConnectableAdvertisement::NonscannableDirected { target } => {
+                Ok(PduBuf::directed(addr, *target))
+            }
+        }
+    }
+}
+
+/// A non-connectable advertisement, to be passed to [`PduBuf::from_advertisement`].
+///
+/// Each variant carries exactly the fields that are legal for the PDU it describes.
+///
+/// [`PduBuf::from_advertisement`]: struct.PduBuf.html#method.from_advertisement
+pub enum NonconnectableAdvertisement<'a> {
+    /// Non-connectable, non-scannable, undirected advertising event (`ADV_NONCONN_IND`).
+    NonscannableUndirected {
+        /// Advertising data to send along with the `ADV_NONCONN_IND`.
+        adv_data: &'a [AdStructure<'a>],
+    },
+    /// Non-connectable, scannable, undirected advertising event (`ADV_SCAN_IND`).
+    ScannableUndirected {
+        /// Advertising data to send along with the `ADV_SCAN_IND`.
+        adv_data: &'a [AdStructure<'a>],
+        /// Scan response data to send if a `SCAN_REQ` is received in reply.
+        scan_data: &'a [AdStructure<'a>],
+    },
+}
+
+impl<'a> NonconnectableAdvertisement<'a> {
+    /// Returns the scan response data to send if this advertisement is scanned, if any.
+    pub fn scan_data(&self) -> Option<&'a [AdStructure<'a>]> {
+        match *self {
+            NonconnectableAdvertisement::ScannableUndirected { scan_data, .. } => Some(scan_data),
+            NonconnectableAdvertisement::NonscannableUndirected { .. } => None,
+        }
+    }
+}
+
+impl<'a> Advertisement for NonconnectableAdvertisement<'a> {
+    fn into_pdu(&self, addr: DeviceAddress) -> Result<PduBuf, Error> {
+        match self {
+            NonconnectableAdvertisement::NonscannableUndirected { adv_data } => {
+                PduBuf::adv(PduType::AdvNonconnInd, addr, &mut adv_data.iter())
+            }
+            NonconnectableAdvertisement::ScannableUndirected { adv_data, .. } => {
+                PduBuf::adv(PduType::AdvScanInd, addr, &mut adv_data.iter())
+            }
+        }
+    }
+}
+
+/// Lets [`PduBuf::from_advertisement`] build a PDU from either a [`ConnectableAdvertisement`] or
+/// a [`NonconnectableAdvertisement`] through a single entry point.
+///
+/// [`PduBuf::from_advertisement`]: struct.PduBuf.html#method.from_advertisement
+/// [`ConnectableAdvertisement`]: enum.ConnectableAdvertisement.html
+/// [`NonconnectableAdvertisement`]: enum.NonconnectableAdvertisement.html
+pub trait Advertisement {
+    /// Builds the advertising PDU `addr` would send for this advertisement.
+    fn into_pdu(&self, addr: DeviceAddress) -> Result<PduBuf, Error>;
+}
+
+/// A received advertising channel PDU, decoded into its typed fields.
+///
+/// This is the receive-side counterpart to [`PduBuf`]: while `PduBuf` builds a PDU to be sent,
+/// `AdvPdu` is the result of [`AdvPdu::parse`]-ing one that was received over the air.
+///
+/// [`PduBuf`]: struct.PduBuf.html
+/// [`AdvPdu::parse`]: enum.AdvPdu.html#method.parse
+#[derive(Debug)]
+pub enum AdvPdu<'a> {
+    /// Connectable undirected advertising event (`ADV_IND`).
+    AdvInd {
+        adv_addr: DeviceAddress,
+        adv_data: &'a [u8],
+    },
+    /// Connectable directed advertising event (`ADV_DIRECT_IND`).
+    AdvDirectInd {
+        adv_addr: DeviceAddress,
+        init_addr: DeviceAddress,
+    },
+    /// Non-connectable undirected advertising event (`ADV_NONCONN_IND`).
+    AdvNonconnInd {
+        adv_addr: DeviceAddress,
+        adv_data: &'a [u8],
+    },
+    /// Scannable undirected advertising event (`ADV_SCAN_IND`).
+    AdvScanInd {
+        adv_addr: DeviceAddress,
+        adv_data: &'a [u8],
+    },
+    /// Scan request (`SCAN_REQ`), sent by a device in Scanning State.
+    ScanReq {
+        scan_addr: DeviceAddress,
+        adv_addr: DeviceAddress,
+    },
+    /// Scan response (`SCAN_RSP`), sent by a device in Advertising State.
+    ScanRsp {
+        adv_addr: DeviceAddress,
+        scan_data: &'a [u8],
+    },
+    /// Connect request (`CONNECT_REQ`), sent by a device in Initiating State.
+    ConnectReq {
+        init_addr: DeviceAddress,
+        adv_addr: DeviceAddress,
+        ll_data: LlData,
+    },
+}
+
+impl<'a> AdvPdu<'a> {
+    /// Parses a received advertising channel PDU.
+    ///
+    /// `header` must be the already-decoded 2-byte header (see [`Header::parse`]), and `payload`
+    /// the bytes that followed it on air. `payload` must be exactly `header.payload_length()`
+    /// bytes long.
+    ///
+    /// Returns `Err` rather than panicking if the header's `Length` field is out of the
+    /// spec-mandated 6..=37 range, if `payload` doesn't match that length, or if an AD structure
+    /// embedded in the payload declares a length that would overrun the buffer.
+    ///
+    /// [`Header::parse`]: struct.Header.html#method.parse
+    pub fn parse(header: Header, payload: &'a [u8]) -> Result<Self, Error> {
+        let length = header.payload_length();
+        if length < 6 || length > 37 || payload.len() != length as usize {
+            return Err(Error::InvalidLength);
+        }
+
+        Ok(match header.type_() {
+            PduType::AdvInd => {
+                let (adv_addr, adv_data) = Self::addr_and_ad_data(header.tx_add(), payload)?;
+                AdvPdu::AdvInd { adv_addr, adv_data }
+            }
+            PduType::AdvNonconnInd => {
+                let (adv_addr, adv_data) = Self::addr_and_ad_data(header.tx_add(), payload)?;
+                AdvPdu::AdvNonconnInd { adv_addr, adv_data }
+            }
+            PduType::AdvScanInd => {
+                let (adv_addr, adv_data) = Self::addr_and_ad_data(header.tx_add(), payload)?;
+                AdvPdu::AdvScanInd { adv_addr, adv_data }
+            }
+            PduType::ScanRsp => {
+                let (adv_addr, scan_data) = Self::addr_and_ad_data(header.tx_add(), payload)?;
+                AdvPdu::ScanRsp {
+                    adv_addr,
+                    scan_data,
+                }
+            }
+            PduType::AdvDirectInd => {
+                if payload.len() != 12 {
+                    return Err(Error::InvalidLength);
+                }
+                let (adv_addr, init_addr) = Self::two_addrs(header, payload)?;
+                AdvPdu::AdvDirectInd {
+                    adv_addr,
+                    init_addr,
+                }
+            }
+            PduType::ScanReq => {
+                if payload.len() != 12 {
+                    return Err(Error::InvalidLength);
+                }
+                let (scan_addr, adv_addr) = Self::two_addrs(header, payload)?;
+                AdvPdu::ScanReq {
+                    scan_addr,
+                    adv_addr,
+                }
+            }
+            PduType::ConnectReq => {
+                if payload.len() != 34 {
+                    return Err(Error::InvalidLength);
+                }
+                let (init_addr, adv_addr) = Self::two_addrs(header, payload)?;
+                let ll_data = LlData::parse(&payload[12..])?;
+                AdvPdu::ConnectReq {
+                    init_addr,
+                    adv_addr,
+                    ll_data,
+                }
+            }
+            // Extended advertising PDUs have a completely different payload layout and must be
+            // parsed with `ExtendedHeader::parse` instead.
+            PduType::AdvExtInd | PduType::AuxConnectRsp | PduType::Unknown(_) => {
+                return Err(Error::InvalidValue)
+            }
+        })
+    }
+
+    /// Splits off the leading device address shared by `ADV_IND`-like PDUs and returns the
+    /// trailing advertising data, after checking that it consists of well-formed AD structures.
+    fn addr_and_ad_data(tx_add: bool, payload: &'a [u8]) -> Result<(DeviceAddress, &'a [u8]), Error> {
+        if payload.len() < 6 {
+            return Err(Error::InvalidLength);
+        }
+        let mut raw = [0; 6];
+        raw.copy_from_slice(&payload[..6]);
+        let data = &payload[6..];
+
+        // Sanity-check that every AD structure fits inside `data` without overrunning it.
+        let mut reader = ByteReader::new(data);
+        while reader.bytes_left() > 0 {
+            AdStructure::from_bytes(&mut reader)?;
+        }
+
+        Ok((DeviceAddress::new(raw, tx_add), data))
+    }
+
+    /// Splits the two 6-byte device addresses shared by directed, scan request and connect
+    /// request PDUs out of `payload` (`AdvA`/`InitA`, `ScanA`/`AdvA` or `InitA`/`AdvA`, in that
+    /// order).
+    fn two_addrs(header: Header, payload: &'a [u8]) -> Result<(DeviceAddress, DeviceAddress), Error> {
+        if payload.len() < 12 {
+            return Err(Error::InvalidLength);
+        }
+        let mut first = [0; 6];
+        first.copy_from_slice(&payload[..6]);
+        let mut second = [0; 6];
+        second.copy_from_slice(&payload[6..12]);
+
+        Ok((
+            DeviceAddress::new(first, header.tx_add()),
+            DeviceAddress::new(second, header.rx_add()),
+        ))
+    }
+}
+
+/// Drives the active-scan procedure against a single advertiser of interest.
+///
+/// This mirrors the role the advertising state has on the other side of the link: where an
+/// advertiser repeatedly transmits its `PduBuf` on the primary channels, a `Scanner` reacts to
+/// what comes back on those same channels, emitting a `SCAN_REQ` as soon as it sees an
+/// `ADV_IND`/`ADV_SCAN_IND` from the device it cares about, and surfacing the matching
+/// `SCAN_RSP` payload once it arrives.
+///
+/// Every [`AdvPdu`] received while scanning must be passed to [`Scanner::receive`], which
+/// returns the next action to take.
+///
+/// [`AdvPdu`]: enum.AdvPdu.html
+/// [`Scanner::receive`]: struct.Scanner.html#method.receive
+pub struct Scanner {
+    scanner_addr: DeviceAddress,
+    adv_addr: DeviceAddress,
+    state: ScanState,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ScanState {
+    /// Waiting for an `ADV_IND`/`ADV_SCAN_IND` from the device of interest.
+    WaitingForAdv,
+    /// A `SCAN_REQ` was just sent; waiting for the matching `SCAN_RSP`.
+    WaitingForScanRsp,
+}
+
+/// The result of feeding a received PDU into a [`Scanner`].
+///
+/// [`Scanner`]: struct.Scanner.html
+#[derive(Debug)]
+pub enum ScanEvent<'a> {
+    /// The PDU was irrelevant to this scan and was ignored: it came from another device, is of a
+    /// type the scan does not act on (such as `ADV_NONCONN_IND` or `ADV_DIRECT_IND`), or is a
+    /// `SCAN_RSP` for which no `SCAN_REQ` is pending.
+    Ignored,
+    /// A `SCAN_REQ` must now be sent to the advertiser, without delay.
+    SendScanRequest(PduBuf),
+    /// The advertiser's scan response data was received, completing the active scan.
+    ScanResponse(&'a [u8]),
+}
+
+impl Scanner {
+    /// Starts actively scanning for advertisements from `adv_addr`.
+    pub fn new(scanner_addr: DeviceAddress, adv_addr: DeviceAddress) -> Self {
+        Self {
+            scanner_addr,
+            adv_addr,
+            state: ScanState::WaitingForAdv,
+        }
+    }
+
+    /// Feeds a received advertising channel PDU into the scan state machine.
+    pub fn receive<'a>(&mut self, pdu: &AdvPdu<'a>) -> Result<ScanEvent<'a>, Error> {
+        match (self.state, pdu) {
+            // An advertisement seen while still waiting for a `SCAN_RSP` means that response was
+            // missed, so the request is sent again.
+            (_, AdvPdu::AdvInd { adv_addr, .. }) | (_, AdvPdu::AdvScanInd { adv_addr, .. })
+                if *adv_addr == self.adv_addr =>
+            {
+                let req = PduBuf::scan_request(self.scanner_addr, self.adv_addr)?;
+                self.state = ScanState::WaitingForScanRsp;
+                Ok(ScanEvent::SendScanRequest(req))
+            }
+            (ScanState::WaitingForScanRsp, AdvPdu::ScanRsp { adv_addr, scan_data })
+                if *adv_addr == self.adv_addr =>
+            {
+                self.state = ScanState::WaitingForAdv;
+                Ok(ScanEvent::ScanResponse(scan_data))
+            }
+            _ => Ok(ScanEvent::Ignored),
+        }
+    }
+}
+
+/// Connection parameters carried in a `CONNECT_REQ` PDU's `LLData` field.
+///
+/// These are the parameters the Link Layer uses from the moment the connection is established,
+/// and are handed straight to the connection manager that drives the new connection.
+#[derive(Debug, Copy, Clone)]
+pub struct LlData {
+    access_address: u32,
+    crc_init: u32,
+    win_size: u8,
+    win_offset: u16,
+    interval: u16,
+    latency: u16,
+    timeout: u16,
+    channel_map: [u8; 5],
+    hop: u8,
+    sca: u8,
+}
+
+impl LlData {
+    /// Creates new `LLData`, checking that all range-limited fields are in spec.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_address`: The Access Address to use on the data channels.
+    /// * `crc_init`: CRC initialization value (only the low 24 bits are significant).
+    /// * `win_size`: Transmit window size, in units of 1.25 ms.
+    /// * `win_offset`: Transmit window offset, in units of 1.25 ms.
+    /// * `interval`: Connection event interval, in units of 1.25 ms (range `6..=3200`).
+    /// * `latency`: Slave latency, in number of connection events.
+    /// * `timeout`: Supervision timeout, in units of 10 ms (range `10..=3200`).
+    /// * `channel_map`: Bitmap of used data channels, one bit per channel (0..36); at least one
+    ///   channel must be marked used.
+    /// * `hop`: Hop increment used to advance the data channel (range `5..=16`).
+    /// * `sca`: Master's Sleep Clock Accuracy index (range `0..=7`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        access_address: u32,
+        crc_init: u32,
+        win_size: u8,
+        win_offset: u16,
+        interval: u16,
+        latency: u16,
+        timeout: u16,
+        channel_map: [u8; 5],
+        hop: u8,
+        sca: u8,
+    ) -> Result<Self, Error> {
+        if interval < 6 || interval > 3200 {
+            return Err(Error::InvalidValue);
+        }
+        if timeout < 10 || timeout > 3200 {
+            return Err(Error::InvalidValue);
+        }
+        if hop < 5 || hop > 16 {
+            return Err(Error::InvalidValue);
+        }
+        if sca > 7 {
+            return Err(Error::InvalidValue);
+        }
+        if channel_map.iter().all(|&byte| byte == 0) {
+            return Err(Error::InvalidValue);
+        }
+
+        Ok(Self {
+            access_address,
+            crc_init: crc_init & 0x00ff_ffff,
+            win_size,
+            win_offset,
+            interval,
+            latency,
+            timeout,
+            channel_map,
+            hop,
+            sca,
+        })
+    }
+
+    /// Returns the Access Address to use on the data channels.
+    pub fn access_address(&self) -> u32 {
+        self.access_address
+    }
+
+    /// Returns the 24-bit CRC initialization value.
+    pub fn crc_init(&self) -> u32 {
+        self.crc_init
+    }
+
+    /// Returns the transmit window size, in units of 1.25 ms.
+    pub fn win_size(&self) -> u8 {
+        self.win_size
+    }
+
+    /// Returns the transmit window offset, in units of 1.25 ms.
+    pub fn win_offset(&self) -> u16 {
+        self.win_offset
+    }
+
+    /// Returns the connection event interval, in units of 1.25 ms.
+    pub fn interval(&self) -> u16 {
+        self.interval
+    }
+
+    /// Returns the slave latency, in number of connection events.
+    pub fn latency(&self) -> u16 {
+        self.latency
+    }
+
+    /// Returns the supervision timeout, in units of 10 ms.
+    pub fn timeout(&self) -> u16 {
+        self.timeout
+    }
+
+    /// Returns the bitmap of used data channels, one bit per channel (0..36).
+    pub fn channel_map(&self) -> &[u8; 5] {
+        &self.channel_map
+    }
+
+    /// Returns the hop increment used to advance the data channel.
+    pub fn hop(&self) -> u8 {
+        self.hop
+    }
+
+    /// Returns the master's Sleep Clock Accuracy index.
+    pub fn sca(&self) -> u8 {
+        self.sca
+    }
+
+    /// Writes this `LLData` to `out`, which must be exactly 22 bytes long.
+    fn to_bytes(&self, out: &mut [u8]) {
+        assert_eq!(out.len(), 22);
+
+        LittleEndian::write_u32(&mut out[0..4], self.access_address);
+        let mut crc_init = [0; 4];
+        LittleEndian::write_u32(&mut crc_init, self.crc_init);
+        out[4..7].copy_from_slice(&crc_init[0..3]);
+        out[7] = self.win_size;
+        LittleEndian::write_u16(&mut out[8..10], self.win_offset);
+        LittleEndian::write_u16(&mut out[10..12], self.interval);
+        LittleEndian::write_u16(&mut out[12..14], self.latency);
+        LittleEndian::write_u16(&mut out[14..16], self.timeout);
+        out[16..21].copy_from_slice(&self.channel_map);
+        out[21] = (self.hop & 0b0001_1111) | (self.sca << 5);
+    }
+
+    /// Parses `LLData` from its 22-byte on-air representation.
+    fn parse(data: &[u8]) -> Result<Self, Error> {
+        if data.len() != 22 {
+            return Err(Error::InvalidLength);
+        }
+
+        let access_address = LittleEndian::read_u32(&data[0..4]);
+        let mut crc_init = [0; 4];
+        crc_init[0..3].copy_from_slice(&data[4..7]);
+        let win_size = data[7];
+        let win_offset = LittleEndian::read_u16(&data[8..10]);
+        let interval = LittleEndian::read_u16(&data[10..12]);
+        let latency = LittleEndian::read_u16(&data[12..14]);
+        let timeout = LittleEndian::read_u16(&data[14..16]);
+        let mut channel_map = [0; 5];
+        channel_map.copy_from_slice(&data[16..21]);
+        let hop_sca = data[21];
+
+        Self::new(
+            access_address,
+            LittleEndian::read_u32(&crc_init),
+            win_size,
+            win_offset,
+            interval,
+            latency,
+            timeout,
+            channel_map,
+            hop_sca & 0b0001_1111,
+            hop_sca >> 5,
+        )
+    }
+}
+
+/// Number of microseconds in one `AdvInterval` unit.
+const ADV_INTERVAL_UNIT_MICROS: u64 = 625;
+
+/// Minimum advertising interval, in units of 625 µs (20 ms).
+const MIN_ADV_INTERVAL: u32 = 0x20;
+
+/// Maximum advertising interval, in units of 625 µs (~10485.76 s).
+const MAX_ADV_INTERVAL: u32 = 0xff_ffff;
+
+/// How often an advertising PDU is repeated on the primary advertising channels, in units of
+/// 625 µs.
+///
+/// Valid range is `0x20..=0xFFFFFF` (20 ms to ~10485.76 s), as mandated by the spec.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AdvInterval(u32);
+
+impl AdvInterval {
+    /// Creates an `AdvInterval` from its raw on-air value, in units of 625 µs.
+    pub fn from_raw(raw: u32) -> Result<Self, Error> {
+        if raw < MIN_ADV_INTERVAL || raw > MAX_ADV_INTERVAL {
+            return Err(Error::InvalidValue);
+        }
+
+        Ok(Self(raw))
+    }
+
+    /// Creates an `AdvInterval` from a `Duration`, rounding down to the nearest 625 µs unit.
+    pub fn from_duration(duration: Duration) -> Result<Self, Error> {
+        let raw = duration.as_micros() / u128::from(ADV_INTERVAL_UNIT_MICROS);
+        if raw < u128::from(MIN_ADV_INTERVAL) || raw > u128::from(MAX_ADV_INTERVAL) {
+            return Err(Error::InvalidValue);
+        }
+        Self::from_raw(raw as u32)
+    }
+
+    /// Returns the raw on-air value, in units of 625 µs.
+    pub fn as_raw(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns this interval as a `Duration`.
+    pub fn as_duration(&self) -> Duration {
+        Duration::from_micros(u64::from(self.0) * ADV_INTERVAL_UNIT_MICROS)
+    }
+}
+
+/// Number of milliseconds in one `AdvDuration` unit.
+const ADV_DURATION_UNIT_MILLIS: u64 = 10;
+
+/// Maximum `AdvDuration`, in units of 10 ms (~655.35 s).
+const MAX_ADV_DURATION: u32 = 0xffff;
+
+/// How long an advertising process keeps repeating before stopping on its own, in units of
+/// 10 ms.
+///
+/// Valid range is `1..=0xFFFF` (10 ms to ~655.35 s); use [`AdvDuration::forever`] for an
+/// advertising process with no defined end.
+///
+/// [`AdvDuration::forever`]: struct.AdvDuration.html#method.forever
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AdvDuration(Option<u16>);
+
+impl AdvDuration {
+    /// Returns an `AdvDuration` for an advertising process that keeps going until explicitly
+    /// stopped.
+    pub fn forever() -> Self {
+        Self(None)
+    }
+
+    /// Creates an `AdvDuration` from its raw on-air value, in units of 10 ms.
+    pub fn from_raw(raw: u16) -> Result<Self, Error> {
+        if raw < 1 {
+            return Err(Error::InvalidValue);
+        }
+
+        Ok(Self(Some(raw)))
+    }
+
+    /// Creates an `AdvDuration` from a `Duration`, rounding down to the nearest 10 ms unit.
+    pub fn from_duration(duration: Duration) -> Result<Self, Error> {
+        let raw = duration.as_millis() / u128::from(ADV_DURATION_UNIT_MILLIS);
+        if raw < 1 || raw > u128::from(MAX_ADV_DURATION) {
+            return Err(Error::InvalidValue);
+        }
+        Self::from_raw(raw as u16)
+    }
+
+    /// Returns `true` if this is `AdvDuration::forever()`, ie. the advertising process has no
+    /// defined end.
+    pub fn is_forever(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns the raw on-air value, in units of 10 ms, or `None` if this is `forever()`.
+    pub fn as_raw(&self) -> Option<u16> {
+        self.0
+    }
+
+    /// Returns this duration as a `Duration`, or `None` if this is `forever()`.
+    pub fn as_duration(&self) -> Option<Duration> {
+        self.0
+            .map(|raw| Duration::from_millis(u64::from(raw) * ADV_DURATION_UNIT_MILLIS))
+    }
+}
+
+/// Parameters controlling the timing of a repeating advertisement on the 3 primary advertising
+/// channels.
+///
+/// Bundles the advertising interval, an optional duration after which the advertising process
+/// stops on its own, and the `PduType` of the PDU being repeated (which determines whether the
+/// advertising set is connectable, scannable and/or directed), so that [`Advertiser`] can compute
+/// when to re-transmit without the caller having to track any of this separately.
+///
+/// [`Advertiser`]: struct.Advertiser.html
+#[derive(Debug, Copy, Clone)]
+pub struct AdvParams {
+    pdu_type: PduType,
+    interval: AdvInterval,
+    duration: AdvDuration,
+}
+
+impl AdvParams {
+    /// Creates new `AdvParams` for PDUs of type `pdu_type`.
+    pub const fn new(pdu_type: PduType, interval: AdvInterval, duration: AdvDuration) -> Self {
+        Self {
+            pdu_type,
+            interval,
+            duration,
+        }
+    }
+
+    /// Creates new `AdvParams` from real `Duration`s, converting and validating them into the
+    /// on-air units.
+    ///
+    /// `duration` is the overall duration of the advertising process, or `None` to advertise
+    /// until explicitly stopped.
+    pub fn from_durations(
+        pdu_type: PduType,
+        interval: Duration,
+        duration: Option<Duration>,
+    ) -> Result<Self, Error> {
+        let interval = AdvInterval::from_duration(interval)?;
+        let duration = match duration {
+            Some(duration) => AdvDuration::from_duration(duration)?,
+            None => AdvDuration::forever(),
+        };
+
+        Ok(Self::new(pdu_type, interval, duration))
+    }
+
+    /// Returns the `PduType` of the advertising PDU being repeated.
+    pub fn pdu_type(&self) -> PduType {
+        self.pdu_type
+    }
+
+    /// Returns the advertising interval.
+    pub fn interval(&self) -> AdvInterval {
+        self.interval
+    }
+
+    /// Returns the advertising duration.
+    pub fn duration(&self) -> AdvDuration {
+        self.duration
+    }
+}
+
+/// The result of advancing an [`Advertiser`]'s clock.
+///
+/// [`Advertiser`]: struct.Advertiser.html
+pub enum AdvertiserEvent<'a> {
+    /// Not yet time to re-transmit, and the advertising process isn't over yet.
+    Idle,
+    /// The advertising interval has elapsed; re-transmit this PDU on all 3 primary advertising
+    /// channels now.
+    Transmit(&'a PduBuf),
+    /// `params.duration()` has elapsed; the advertising process is complete.
+    Done,
+}
+
+/// Drives the timing of a repeating advertising PDU on the 3 primary advertising channels.
+///
+/// Wraps a [`PduBuf`] together with the [`AdvParams`] describing how often to repeat it and for
+/// how long, and tracks elapsed time so the stack knows when the next transmission is due and
+/// when the advertising process has run its course, rather than leaving timing entirely to the
+/// caller.
+///
+/// [`PduBuf`]: struct.PduBuf.html
+/// [`AdvParams`]: struct.AdvParams.html
+pub struct Advertiser {
+    pdu: PduBuf,
+    params: AdvParams,
+    since_last_tx: Duration,
+    total_elapsed: Duration,
+}
+
+impl Advertiser {
+    /// Starts repeating `pdu` according to `params`.
+    ///
+    /// Returns `Error::InvalidValue` if `params.pdu_type()` doesn't match the `PduType` of
+    /// `pdu`'s header, since the two must describe the same advertisement.
+    pub fn new(pdu: PduBuf, params: AdvParams) -> Result<Self, Error> {
+        if params.pdu_type() != pdu.header().type_() {
+            return Err(Error::InvalidValue);
+        }
+
+        Ok(Self {
+            pdu,
+            params,
+            since_last_tx: Duration::from_secs(0),
+            total_elapsed: Duration::from_secs(0),
+        })
+    }
 
-impl fmt::Debug for PduBuf {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({:?}, {:?})", self.header(), self.payload())
+    /// Returns the `AdvParams` driving this advertiser.
+    pub fn params(&self) -> AdvParams {
+        self.params
+    }
+
+    /// Advances the advertiser's internal clock by `dt` and returns the next action to take.
+    ///
+    /// The caller is expected to call this often enough that `dt` is small compared to the
+    /// advertising interval; the advertiser itself does not schedule anything on its own.
+    pub fn advance(&mut self, dt: Duration) -> AdvertiserEvent<'_> {
+        self.total_elapsed += dt;
+        if let Some(duration) = self.params.duration().as_duration() {
+            if self.total_elapsed >= duration {
+                return AdvertiserEvent::Done;
+            }
+        }
+
+        self.since_last_tx += dt;
+        if self.since_last_tx >= self.params.interval().as_duration() {
+            self.since_last_tx = Duration::from_secs(0);
+            return AdvertiserEvent::Transmit(&self.pdu);
+        }
+
+        AdvertiserEvent::Idle
     }
 }
 
@@ -300,6 +1076,25 @@ impl Header {
         let header = self.0 & !0b00111111_00000000;
         self.0 = header | ((length as u16) << 8);
     }
+
+    /// Returns the length of the payload in octets, as specified in the `Length` field of an
+    /// extended advertising PDU's header.
+    ///
+    /// Extended advertising PDUs (see [`PduType::AdvExtInd`]) reinterpret the 2 bits that are
+    /// reserved in the legacy format as the high bits of the `Length` field, which allows
+    /// payloads up to 255 bytes instead of the legacy format's 37.
+    ///
+    /// [`PduType::AdvExtInd`]: enum.PduType.html#variant.AdvExtInd
+    pub fn ext_payload_length(&self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    /// Sets the payload length of an extended advertising PDU. See [`ext_payload_length`].
+    ///
+    /// [`ext_payload_length`]: struct.Header.html#method.ext_payload_length
+    pub fn set_ext_payload_length(&mut self, length: u8) {
+        self.0 = (self.0 & 0x00ff) | (u16::from(length) << 8);
+    }
 }
 
 impl fmt::Debug for Header {
@@ -320,7 +1115,7 @@ enum_with_unknown! {
     ///
     /// [`Header`]: struct.Header.html
     /// [`PduBuf`]: struct.PduBuf.html
-    #[derive(Debug)]
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
     pub enum PduType(u8) {
         /// Connectable undirected advertising event.
         AdvInd = 0b0000,
@@ -335,16 +1130,873 @@ enum_with_unknown! {
         ///
         /// Sent by device in Scanning State, received by device in Advertising
         /// State.
+        ///
+        /// Also used as `AUX_SCAN_REQ` on the secondary advertising channels, which is otherwise
+        /// identical but answered by an `AUX_SCAN_RSP` instead of a `SCAN_RSP`.
         ScanReq = 0b0011,
         /// Scan response.
         ///
         /// Sent by device in Advertising State, received by devicein Scanning
         /// State.
+        ///
+        /// The extended advertising `AUX_SCAN_RSP` is not of this type; it is sent as an
+        /// [`AdvExtInd`](#variant.AdvExtInd) PDU instead.
         ScanRsp = 0b0100,
         /// Connect request.
         ///
         /// Sent by device in Initiating State, received by device in
         /// Advertising State.
+        ///
+        /// Also used as `AUX_CONNECT_REQ` on the secondary advertising channels, which is
+        /// otherwise identical but answered by an `AUX_CONNECT_RSP` instead of a legacy
+        /// `CONNECT_REQ`'s implicit acceptance.
         ConnectReq = 0b0101,
+
+        /// Extended advertising PDU.
+        ///
+        /// BLE 5 extended advertising reuses this single PDU type for `ADV_EXT_IND` (sent on the
+        /// primary advertising channels) as well as `AUX_ADV_IND`, `AUX_SCAN_RSP`, `AUX_SYNC_IND`
+        /// and `AUX_CHAIN_IND` (all sent on a secondary advertising channel); which of these a given
+        /// PDU is is determined by the `AdvMode` field in its Extended Header and by the channel
+        /// and context it was received in, not by anything in this header. See [`ExtendedHeader`].
+        ///
+        /// [`ExtendedHeader`]: struct.ExtendedHeader.html
+        AdvExtInd = 0b0111,
+        /// Auxiliary connection response.
+        ///
+        /// Sent by the device in Advertising State in response to an `AUX_CONNECT_REQ` (a
+        /// `ConnectReq` PDU received on a secondary advertising channel), completing the
+        /// extended-advertising connection setup handshake.
+        AuxConnectRsp = 0b1000,
+    }
+}
+
+/// Maximum payload size of an extended advertising PDU.
+///
+/// Unlike the legacy format's 37-byte limit, extended advertising PDUs (see
+/// [`PduType::AdvExtInd`]) use the full second header byte for `Length`, allowing payloads up to
+/// 255 bytes.
+///
+/// [`PduType::AdvExtInd`]: enum.PduType.html#variant.AdvExtInd
+const MAX_EXT_PAYLOAD_SIZE: usize = 255;
+
+enum_with_unknown! {
+    /// 2-bit advertising mode carried in an extended advertising PDU's [`ExtendedHeader`].
+    ///
+    /// Encodes whether the advertising set being described is connectable and/or scannable;
+    /// directed vs. undirected is instead signalled by the presence of `TargetA`.
+    ///
+    /// [`ExtendedHeader`]: struct.ExtendedHeader.html
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum AdvMode(u8) {
+        /// Neither connectable nor scannable.
+        NonConnectableNonScannable = 0b00,
+        /// Connectable, but not scannable.
+        Connectable = 0b01,
+        /// Scannable, but not connectable.
+        Scannable = 0b10,
+    }
+}
+
+/// Units the [`AuxPtr::aux_offset`] field is expressed in.
+///
+/// [`AuxPtr::aux_offset`]: struct.AuxPtr.html#method.aux_offset
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OffsetUnits {
+    /// 30 microsecond units.
+    Us30,
+    /// 300 microsecond units.
+    Us300,
+}
+
+/// Points from an extended advertising PDU to the auxiliary packet that continues it.
+///
+/// Carried in the `AuxPtr` field of an [`ExtendedHeader`]; decodes the channel, clock accuracy,
+/// offset units and timing offset a scanner or initiator needs in order to receive the next
+/// packet in the chain.
+///
+/// [`ExtendedHeader`]: struct.ExtendedHeader.html
+#[derive(Debug, Copy, Clone)]
+pub struct AuxPtr {
+    channel_index: u8,
+    ca: bool,
+    offset_units: OffsetUnits,
+    aux_offset: u16,
+    aux_phy: u8,
+}
+
+impl AuxPtr {
+    /// Creates an `AuxPtr` describing the timing of an auxiliary packet.
+    ///
+    /// * `channel_index`: Advertising channel index the auxiliary packet will be sent on (range
+    ///   `0..=39`).
+    /// * `ca`: Advertiser's clock accuracy (`true` if within 0..50 ppm, `false` if unknown).
+    /// * `offset_units`: Units `aux_offset` is expressed in.
+    /// * `aux_offset`: Time from the start of this packet until the start of the auxiliary
+    ///   packet, in units of `offset_units` (range `0..=0x1fff`).
+    /// * `aux_phy`: PHY the auxiliary packet will be sent on.
+    pub fn new(
+        channel_index: u8,
+        ca: bool,
+        offset_units: OffsetUnits,
+        aux_offset: u16,
+        aux_phy: u8,
+    ) -> Result<Self, Error> {
+        if channel_index > 39 {
+            return Err(Error::InvalidValue);
+        }
+        if aux_offset > 0x1fff {
+            return Err(Error::InvalidValue);
+        }
+        if aux_phy > 0b111 {
+            return Err(Error::InvalidValue);
+        }
+
+        Ok(Self {
+            channel_index,
+            ca,
+            offset_units,
+            aux_offset,
+            aux_phy,
+        })
+    }
+
+    /// Returns the advertising channel index the auxiliary packet will be sent on.
+    pub fn channel_index(&self) -> u8 {
+        self.channel_index
+    }
+
+    /// Returns the advertiser's clock accuracy (`true` if within 0..50 ppm, `false` if unknown).
+    pub fn clock_accuracy(&self) -> bool {
+        self.ca
+    }
+
+    /// Returns the units `aux_offset` is expressed in.
+    pub fn offset_units(&self) -> OffsetUnits {
+        self.offset_units
+    }
+
+    /// Returns the time from the start of this packet until the start of the auxiliary packet,
+    /// in units of [`offset_units`].
+    ///
+    /// [`offset_units`]: struct.AuxPtr.html#method.offset_units
+    pub fn aux_offset(&self) -> u16 {
+        self.aux_offset
+    }
+
+    /// Returns the PHY the auxiliary packet will be sent on.
+    pub fn aux_phy(&self) -> u8 {
+        self.aux_phy
+    }
+
+    /// Parses a 3-byte `AuxPtr` field.
+    fn parse(data: &[u8]) -> Result<Self, Error> {
+        if data.len() != 3 {
+            return Err(Error::InvalidLength);
+        }
+        let raw = u32::from(data[0]) | (u32::from(data[1]) << 8) | (u32::from(data[2]) << 16);
+
+        Ok(Self {
+            channel_index: (raw & 0b0011_1111) as u8,
+            ca: raw & (1 << 6) != 0,
+            offset_units: if raw & (1 << 7) != 0 {
+                OffsetUnits::Us300
+            } else {
+                OffsetUnits::Us30
+            },
+            aux_offset: ((raw >> 8) & 0x1fff) as u16,
+            aux_phy: ((raw >> 21) & 0b0111) as u8,
+        })
+    }
+
+    /// Writes this `AuxPtr` to `out`, which must be exactly 3 bytes long.
+    fn to_bytes(&self, out: &mut [u8]) {
+        assert_eq!(out.len(), 3);
+
+        let mut raw = u32::from(self.channel_index & 0b0011_1111);
+        if self.ca {
+            raw |= 1 << 6;
+        }
+        if self.offset_units == OffsetUnits::Us300 {
+            raw |= 1 << 7;
+        }
+        raw |= u32::from(self.aux_offset & 0x1fff) << 8;
+        raw |= u32::from(self.aux_phy & 0b0111) << 21;
+
+        out[0] = raw as u8;
+        out[1] = (raw >> 8) as u8;
+        out[2] = (raw >> 16) as u8;
+    }
+}
+
+/// The Extended Header carried at the start of an extended advertising PDU's payload.
+///
+/// Lays out a 1-byte `AdvMode`/length field, followed (if the length is non-zero) by a 1-byte
+/// flags field selecting which of the fixed-order optional subfields below are present, any
+/// ACAD, and finally the advertising data:
+///
+/// `AdvA` (6) -> `TargetA` (6) -> `CTEInfo` (1) -> `AdvDataInfo` (2) -> `AuxPtr` (3) ->
+/// `SyncInfo` (18) -> `TxPower` (1) -> ACAD -> advertising data.
+#[derive(Debug, Clone)]
+pub struct ExtendedHeader<'a> {
+    adv_mode: AdvMode,
+    adv_a: Option<DeviceAddress>,
+    target_a: Option<DeviceAddress>,
+    cte_info: Option<u8>,
+    adv_data_info: Option<u16>,
+    aux_ptr: Option<AuxPtr>,
+    sync_info: Option<[u8; 18]>,
+    tx_power: Option<i8>,
+    acad: &'a [u8],
+    adv_data: &'a [u8],
+}
+
+impl<'a> ExtendedHeader<'a> {
+    /// Returns the advertising mode (connectable/scannable) of the advertising set.
+    pub fn adv_mode(&self) -> AdvMode {
+        self.adv_mode
+    }
+
+    /// Returns the advertiser's address, if present.
+    pub fn adv_a(&self) -> Option<DeviceAddress> {
+        self.adv_a
+    }
+
+    /// Returns the target's address, if this is a directed PDU.
+    pub fn target_a(&self) -> Option<DeviceAddress> {
+        self.target_a
+    }
+
+    /// Returns the raw `AdvDataInfo` field (DID + SID), if present.
+    pub fn adv_data_info(&self) -> Option<u16> {
+        self.adv_data_info
+    }
+
+    /// Returns the pointer to the next packet in the chain, if this PDU is followed by one.
+    pub fn aux_ptr(&self) -> Option<AuxPtr> {
+        self.aux_ptr
+    }
+
+    /// Returns the raw `SyncInfo` field used for Periodic Advertising, if present.
+    pub fn sync_info(&self) -> Option<[u8; 18]> {
+        self.sync_info
+    }
+
+    /// Returns the advertiser's transmit power in dBm, if present.
+    pub fn tx_power(&self) -> Option<i8> {
+        self.tx_power
+    }
+
+    /// Returns the Additional Controller Advertising Data, if any.
+    pub fn acad(&self) -> &'a [u8] {
+        self.acad
+    }
+
+    /// Returns the advertising data carried after the Extended Header.
+    pub fn adv_data(&self) -> &'a [u8] {
+        self.adv_data
+    }
+
+    /// Parses the Extended Header and trailing advertising data out of `payload`.
+    ///
+    /// `header` must be the 2-byte PDU header received alongside `payload`, and `payload` must be
+    /// exactly `header.ext_payload_length()` bytes, as received on-air.
+    pub fn parse(header: Header, payload: &'a [u8]) -> Result<Self, Error> {
+        if payload.is_empty() || payload.len() != header.ext_payload_length() as usize {
+            return Err(Error::InvalidLength);
+        }
+
+        let adv_mode = AdvMode::from(payload[0] >> 6);
+        let header_len = (payload[0] & 0b0011_1111) as usize;
+        if 1 + header_len > payload.len() {
+            return Err(Error::InvalidLength);
+        }
+        let adv_data = &payload[1 + header_len..];
+
+        let mut ext = ExtendedHeader {
+            adv_mode,
+            adv_a: None,
+            target_a: None,
+            cte_info: None,
+            adv_data_info: None,
+            aux_ptr: None,
+            sync_info: None,
+            tx_power: None,
+            acad: &[],
+            adv_data,
+        };
+
+        if header_len == 0 {
+            return Ok(ext);
+        }
+
+        let mut fields = &payload[2..1 + header_len];
+        let flags = payload[1];
+
+        macro_rules! take {
+            ($n:expr) => {{
+                if fields.len() < $n {
+                    return Err(Error::InvalidLength);
+                }
+                let (head, tail) = fields.split_at($n);
+                fields = tail;
+                head
+            }};
+        }
+
+        if flags & 0b0000_0001 != 0 {
+            let mut raw = [0; 6];
+            raw.copy_from_slice(take!(6));
+            ext.adv_a = Some(DeviceAddress::new(raw, header.tx_add()));
+        }
+        if flags & 0b0000_0010 != 0 {
+            let mut raw = [0; 6];
+            raw.copy_from_slice(take!(6));
+            ext.target_a = Some(DeviceAddress::new(raw, header.rx_add()));
+        }
+        if flags & 0b0000_0100 != 0 {
+            ext.cte_info = Some(take!(1)[0]);
+        }
+        if flags & 0b0000_1000 != 0 {
+            ext.adv_data_info = Some(LittleEndian::read_u16(take!(2)));
+        }
+        if flags & 0b0001_0000 != 0 {
+            ext.aux_ptr = Some(AuxPtr::parse(take!(3))?);
+        }
+        if flags & 0b0010_0000 != 0 {
+            let mut raw = [0; 18];
+            raw.copy_from_slice(take!(18));
+            ext.sync_info = Some(raw);
+        }
+        if flags & 0b0100_0000 != 0 {
+            ext.tx_power = Some(take!(1)[0] as i8);
+        }
+        ext.acad = fields;
+
+        Ok(ext)
+    }
+}
+
+/// Stores an extended advertising channel PDU (`ADV_EXT_IND`, `AUX_ADV_IND`, `AUX_CHAIN_IND`,
+/// ...).
+///
+/// This is the BLE 5 counterpart to [`PduBuf`], used for PDUs whose payload no longer fits the
+/// legacy format's 37-byte limit. Build a chain of these with [`ExtAdvChain`].
+///
+/// [`PduBuf`]: struct.PduBuf.html
+/// [`ExtAdvChain`]: struct.ExtAdvChain.html
+pub struct ExtPduBuf {
+    header: Header,
+    payload_buf: [u8; MAX_EXT_PAYLOAD_SIZE],
+    payload_len: usize,
+    /// Offset of the placeholder `AuxPtr` within `payload_buf`, if this PDU points at a
+    /// following one, for use by [`set_aux_ptr`].
+    ///
+    /// [`set_aux_ptr`]: #method.set_aux_ptr
+    aux_ptr_offset: Option<usize>,
+}
+
+impl ExtPduBuf {
+    pub fn header(&self) -> Header {
+        self.header
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.payload_buf[..self.payload_len]
+    }
+
+    /// Overwrites this PDU's `AuxPtr`, if it has one, with real inter-packet timing.
+    ///
+    /// [`ExtAdvChain`] fills in a placeholder `AuxPtr` pointing at zero offset when it builds a
+    /// PDU that chains to another one; call this once the real transmission schedule for the
+    /// next PDU is known, before transmitting this one.
+    ///
+    /// Returns `Error::InvalidValue` if this PDU doesn't carry an `AuxPtr` (i.e. it's the last
+    /// one in the chain).
+    ///
+    /// [`ExtAdvChain`]: struct.ExtAdvChain.html
+    pub fn set_aux_ptr(&mut self, aux_ptr: AuxPtr) -> Result<(), Error> {
+        let offset = self.aux_ptr_offset.ok_or(Error::InvalidValue)?;
+        aux_ptr.to_bytes(&mut self.payload_buf[offset..offset + 3]);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ExtPduBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?}, {:?})", self.header(), self.payload())
+    }
+}
+
+/// Splits advertising data too large for a single legacy PDU across a chain of extended
+/// advertising PDUs.
+///
+/// Yields an `ADV_EXT_IND` pointing at an `AUX_ADV_IND`, followed by as many `AUX_CHAIN_IND`
+/// PDUs as are needed to carry all of `adv_data`. Each yielded [`ExtPduBuf`] must be transmitted,
+/// in order, before the next one is produced; the advertiser has until the time encoded in each
+/// PDU's `AuxPtr` to transmit the next link in the chain.
+///
+/// Note that the `AuxPtr`s this produces carry a placeholder timing offset of zero; the caller's
+/// scheduler, which alone knows the real inter-packet timing, must patch them in via
+/// [`ExtPduBuf::set_aux_ptr`] before transmission.
+///
+/// [`ExtPduBuf::set_aux_ptr`]: struct.ExtPduBuf.html#method.set_aux_ptr
+///
+/// [`ExtPduBuf`]: struct.ExtPduBuf.html
+pub struct ExtAdvChain<'a> {
+    adv_addr: DeviceAddress,
+    adv_mode: AdvMode,
+    remaining: &'a [AdStructure<'a>],
+    state: ChainState,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ChainState {
+    AdvExtInd,
+    AuxAdvInd,
+    AuxChainInd,
+    Done,
+}
+
+impl<'a> ExtAdvChain<'a> {
+    /// Starts building a chain of extended advertising PDUs carrying `adv_data`.
+    pub fn new(adv_addr: DeviceAddress, adv_mode: AdvMode, adv_data: &'a [AdStructure<'a>]) -> Self {
+        Self {
+            adv_addr,
+            adv_mode,
+            remaining: adv_data,
+            state: ChainState::AdvExtInd,
+        }
+    }
+
+    /// Builds the initial `ADV_EXT_IND`, which carries no advertising data of its own and only
+    /// points at the `AUX_ADV_IND` that follows.
+    fn build_adv_ext_ind(&self) -> ExtPduBuf {
+        let mut payload = [0; MAX_EXT_PAYLOAD_SIZE];
+        payload[2..8].copy_from_slice(self.adv_addr.raw());
+
+        let aux_ptr = AuxPtr {
+            channel_index: 0,
+            ca: false,
+            offset_units: OffsetUnits::Us30,
+            aux_offset: 0,
+            aux_phy: 0,
+        };
+        aux_ptr.to_bytes(&mut payload[8..11]);
+
+        let fields_len = 6 + 3;
+        payload[0] = (u8::from(self.adv_mode) << 6) | (1 + fields_len);
+        payload[1] = 0b0000_0001 | 0b0001_0000; // AdvA, AuxPtr
+
+        let total_len = 2 + fields_len as usize;
+        let mut header = Header::new(PduType::AdvExtInd);
+        header.set_ext_payload_length(total_len as u8);
+        header.set_tx_add(self.adv_addr.is_random());
+
+        ExtPduBuf {
+            header,
+            payload_buf: payload,
+            payload_len: total_len,
+            aux_ptr_offset: Some(8),
+        }
+    }
+
+    /// Builds an `AUX_ADV_IND` or `AUX_CHAIN_IND`, filling in as much of `self.remaining` as
+    /// fits, and chaining onward via an `AuxPtr` if anything is left afterwards.
+    fn build_data_segment(&mut self, include_adv_a: bool) -> Result<ExtPduBuf, Error> {
+        let mut payload = [0; MAX_EXT_PAYLOAD_SIZE];
+
+        let mut flags = 0u8;
+        let mut fields_len = 0usize;
+        if include_adv_a {
+            payload[2..8].copy_from_slice(self.adv_addr.raw());
+            fields_len += 6;
+            flags |= 0b0000_0001;
+        }
+
+        let data_start = 2 + fields_len;
+        let (data_len, rest) = {
+            let mut writer = ByteWriter::new(&mut payload[data_start..]);
+            let rest = Self::fill_ad_structures(&mut writer, self.remaining)?;
+            ((MAX_EXT_PAYLOAD_SIZE - data_start) - writer.space_left(), rest)
+        };
+
+        let (data_len, rest, aux_ptr_offset) = if rest.is_empty() {
+            self.state = ChainState::Done;
+            (data_len, rest, None)
+        } else {
+            // Not everything fit: redo the fill, reserving 3 bytes right before the advertising
+            // data for an AuxPtr to the next `AUX_CHAIN_IND`.
+            let data_start = data_start + 3;
+            let (data_len, rest) = {
+                let mut writer = ByteWriter::new(&mut payload[data_start..]);
+                let rest = Self::fill_ad_structures(&mut writer, self.remaining)?;
+                ((MAX_EXT_PAYLOAD_SIZE - data_start) - writer.space_left(), rest)
+            };
+            if rest.len() == self.remaining.len() {
+                // Not even a single AD structure fits alongside the Extended Header and an AuxPtr.
+                return Err(Error::InvalidLength);
+            }
+
+            let aux_ptr = AuxPtr {
+                channel_index: 0,
+                ca: false,
+                offset_units: OffsetUnits::Us30,
+                aux_offset: 0,
+                aux_phy: 0,
+            };
+            aux_ptr.to_bytes(&mut payload[data_start - 3..data_start]);
+            flags |= 0b0001_0000;
+            fields_len += 3;
+            self.state = ChainState::AuxChainInd;
+            (data_len, rest, Some(data_start - 3))
+        };
+
+        let header_len = 1 + fields_len;
+        payload[0] = (u8::from(self.adv_mode) << 6) | header_len as u8;
+        payload[1] = flags;
+
+        self.remaining = rest;
+
+        let total_len = 2 + fields_len + data_len;
+        let mut header = Header::new(PduType::AdvExtInd);
+        header.set_ext_payload_length(total_len as u8);
+        header.set_tx_add(self.adv_addr.is_random());
+
+        Ok(ExtPduBuf {
+            header,
+            payload_buf: payload,
+            payload_len: total_len,
+            aux_ptr_offset,
+        })
+    }
+
+    /// Writes as many complete AD structures from `data` into `writer` as fit, returning the
+    /// ones that didn't.
+    fn fill_ad_structures<'b, 'c>(
+        writer: &mut ByteWriter,
+        data: &'b [AdStructure<'c>],
+    ) -> Result<&'b [AdStructure<'c>], Error> {
+        let mut i = 0;
+        while i < data.len() {
+            let mut scratch = [0; MAX_EXT_PAYLOAD_SIZE];
+            let len = {
+                let mut scratch_writer = ByteWriter::new(&mut scratch);
+                data[i].to_bytes(&mut scratch_writer)?;
+                MAX_EXT_PAYLOAD_SIZE - scratch_writer.space_left()
+            };
+            if len > writer.space_left() {
+                break;
+            }
+            data[i].to_bytes(writer)?;
+            i += 1;
+        }
+        Ok(&data[i..])
+    }
+}
+
+impl<'a> Iterator for ExtAdvChain<'a> {
+    type Item = Result<ExtPduBuf, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.state {
+            ChainState::Done => None,
+            ChainState::AdvExtInd => {
+                self.state = ChainState::AuxAdvInd;
+                Some(Ok(self.build_adv_ext_ind()))
+            }
+            ChainState::AuxAdvInd => Some(self.build_data_segment(true)),
+            ChainState::AuxChainInd => Some(self.build_data_segment(false)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(last_byte: u8, random: bool) -> DeviceAddress {
+        DeviceAddress::new([1, 2, 3, 4, 5, last_byte], random)
+    }
+
+    #[test]
+    fn adv_ind_round_trips_through_parse() {
+        let a = addr(0x01, true);
+        let data = [AdStructure::Unknown {
+            ty: 0xaa,
+            data: &[1, 2, 3],
+        }];
+        let adv = ConnectableAdvertisement::ScannableUndirected {
+            adv_data: &data,
+            scan_data: &[],
+        };
+        let pdu = PduBuf::from_advertisement(a, &adv).unwrap();
+
+        match AdvPdu::parse(pdu.header(), pdu.payload()).unwrap() {
+            AdvPdu::AdvInd { adv_addr, adv_data } => {
+                assert_eq!(adv_addr, a);
+                assert!(!adv_data.is_empty());
+            }
+            other => panic!("unexpected variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn connectable_nonscannable_directed_builds_adv_direct_ind() {
+        let a = addr(0x01, true);
+        let target = addr(0x02, false);
+        let adv = ConnectableAdvertisement::NonscannableDirected { target };
+        let pdu = PduBuf::from_advertisement(a, &adv).unwrap();
+
+        assert_eq!(pdu.header().type_(), PduType::AdvDirectInd);
+        assert!(pdu.header().tx_add());
+        assert!(!pdu.header().rx_add());
+
+        match AdvPdu::parse(pdu.header(), pdu.payload()).unwrap() {
+            AdvPdu::AdvDirectInd {
+                adv_addr,
+                init_addr,
+            } => {
+                assert_eq!(adv_addr, a);
+                assert_eq!(init_addr, target);
+            }
+            other => panic!("unexpected variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn nonconnectable_nonscannable_undirected_builds_adv_nonconn_ind() {
+        let a = addr(0x01, false);
+        let data = [];
+        let adv = NonconnectableAdvertisement::NonscannableUndirected { adv_data: &data };
+        let pdu = PduBuf::from_advertisement(a, &adv).unwrap();
+
+        assert_eq!(pdu.header().type_(), PduType::AdvNonconnInd);
+        assert!(!pdu.header().tx_add());
+
+        match AdvPdu::parse(pdu.header(), pdu.payload()).unwrap() {
+            AdvPdu::AdvNonconnInd { adv_addr, .. } => assert_eq!(adv_addr, a),
+            other => panic!("unexpected variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn nonconnectable_scannable_undirected_builds_adv_scan_ind() {
+        let a = addr(0x01, true);
+        let data = [];
+        let adv = NonconnectableAdvertisement::ScannableUndirected {
+            adv_data: &data,
+            scan_data: &data,
+        };
+        let pdu = PduBuf::from_advertisement(a, &adv).unwrap();
+
+        assert_eq!(pdu.header().type_(), PduType::AdvScanInd);
+        assert!(pdu.header().tx_add());
+
+        match AdvPdu::parse(pdu.header(), pdu.payload()).unwrap() {
+            AdvPdu::AdvScanInd { adv_addr, .. } => assert_eq!(adv_addr, a),
+            other => panic!("unexpected variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn adv_direct_ind_rejects_payload_longer_than_the_fixed_addr_pair() {
+        let mut header = Header::new(PduType::AdvDirectInd);
+        header.set_payload_length(13);
+        let payload = [0; 13];
+
+        assert!(AdvPdu::parse(header, &payload).is_err());
+    }
+
+    #[test]
+    fn scan_req_rejects_payload_longer_than_the_fixed_addr_pair() {
+        let mut header = Header::new(PduType::ScanReq);
+        header.set_payload_length(13);
+        let payload = [0; 13];
+
+        assert!(AdvPdu::parse(header, &payload).is_err());
+    }
+
+    #[test]
+    fn adv_interval_from_duration_rejects_out_of_range_without_wrapping() {
+        // ~31 days, whose raw 625 µs unit count overflows a `u32` by a small, in-range amount.
+        // Truncating before validating would wrap this into a spuriously valid interval.
+        let duration = Duration::from_micros(
+            (u64::from(u32::MAX) + 1 + u64::from(MIN_ADV_INTERVAL)) * ADV_INTERVAL_UNIT_MICROS,
+        );
+
+        assert!(AdvInterval::from_duration(duration).is_err());
+    }
+
+    #[test]
+    fn adv_interval_from_duration_round_trips_in_range() {
+        let duration = Duration::from_millis(100);
+        let interval = AdvInterval::from_duration(duration).unwrap();
+
+        assert_eq!(interval.as_duration(), duration);
+    }
+
+    #[test]
+    fn ext_adv_chain_preserves_adv_addr_randomness_through_parse() {
+        let a = addr(0x01, true);
+        let ad = [AdStructure::Unknown {
+            ty: 0xaa,
+            data: &[1, 2, 3],
+        }];
+        let mut chain = ExtAdvChain::new(a, AdvMode::Connectable, &ad);
+
+        let adv_ext_ind = chain.next().unwrap().unwrap();
+        let ext = ExtendedHeader::parse(adv_ext_ind.header(), adv_ext_ind.payload()).unwrap();
+        assert_eq!(ext.adv_a(), Some(a));
+    }
+
+    #[test]
+    fn ext_pdu_buf_set_aux_ptr_patches_the_placeholder() {
+        let a = addr(0x01, false);
+        let ad = [];
+        let mut chain = ExtAdvChain::new(a, AdvMode::Connectable, &ad);
+        let mut adv_ext_ind = chain.next().unwrap().unwrap();
+
+        let real_aux_ptr = AuxPtr::new(3, true, OffsetUnits::Us30, 42, 0).unwrap();
+        adv_ext_ind.set_aux_ptr(real_aux_ptr).unwrap();
+
+        let ext = ExtendedHeader::parse(adv_ext_ind.header(), adv_ext_ind.payload()).unwrap();
+        let aux_ptr = ext.aux_ptr().unwrap();
+        assert_eq!(aux_ptr.channel_index(), 3);
+        assert_eq!(aux_ptr.aux_offset(), 42);
+    }
+
+    #[test]
+    fn aux_ptr_new_rejects_out_of_range_aux_phy() {
+        assert!(AuxPtr::new(0, false, OffsetUnits::Us30, 0, 0b111).is_ok());
+        assert!(AuxPtr::new(0, false, OffsetUnits::Us30, 0, 0b1000).is_err());
+    }
+
+    #[test]
+    fn advertiser_rejects_mismatched_pdu_type() {
+        let a = addr(0x01, false);
+        let data = [];
+        let pdu = PduBuf::from_advertisement(
+            a,
+            &ConnectableAdvertisement::ScannableUndirected {
+                adv_data: &data,
+                scan_data: &data,
+            },
+        )
+        .unwrap();
+        let params = AdvParams::new(
+            PduType::AdvScanInd,
+            AdvInterval::from_raw(MIN_ADV_INTERVAL).unwrap(),
+            AdvDuration::forever(),
+        );
+
+        assert!(Advertiser::new(pdu, params).is_err());
+    }
+
+    #[test]
+    fn ll_data_round_trips_through_connect_req() {
+        let initiator = addr(0x01, true);
+        let advertiser = addr(0x02, false);
+        let ll_data = LlData::new(
+            0x8e89bed6,
+            0x0000_1234,
+            4,
+            8,
+            40,
+            5,
+            200,
+            [0xff, 0xff, 0xff, 0xff, 0x1f],
+            7,
+            3,
+        )
+        .unwrap();
+        let pdu = PduBuf::connect_request(initiator, advertiser, &ll_data).unwrap();
+
+        match AdvPdu::parse(pdu.header(), pdu.payload()).unwrap() {
+            AdvPdu::ConnectReq {
+                init_addr,
+                adv_addr,
+                ll_data: parsed,
+            } => {
+                assert_eq!(init_addr, initiator);
+                assert_eq!(adv_addr, advertiser);
+                assert_eq!(parsed.access_address(), ll_data.access_address());
+                assert_eq!(parsed.crc_init(), ll_data.crc_init());
+                assert_eq!(parsed.interval(), ll_data.interval());
+                assert_eq!(parsed.hop(), ll_data.hop());
+                assert_eq!(parsed.sca(), ll_data.sca());
+            }
+            other => panic!("unexpected variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn ll_data_rejects_all_zero_channel_map() {
+        let result = LlData::new(
+            0x8e89bed6,
+            0x0000_1234,
+            4,
+            8,
+            40,
+            5,
+            200,
+            [0, 0, 0, 0, 0],
+            7,
+            3,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn scanner_sends_scan_request_then_completes_on_scan_rsp() {
+        let scanner_addr = addr(0x01, false);
+        let adv_addr = addr(0x02, true);
+        let mut scanner = Scanner::new(scanner_addr, adv_addr);
+
+        let adv_pdu = AdvPdu::AdvInd {
+            adv_addr,
+            adv_data: &[],
+        };
+        match scanner.receive(&adv_pdu).unwrap() {
+            ScanEvent::SendScanRequest(req) => {
+                match AdvPdu::parse(req.header(), req.payload()).unwrap() {
+                    AdvPdu::ScanReq {
+                        scan_addr,
+                        adv_addr: req_adv_addr,
+                    } => {
+                        assert_eq!(scan_addr, scanner_addr);
+                        assert_eq!(req_adv_addr, adv_addr);
+                    }
+                    other => panic!("unexpected variant: {:?}", other),
+                }
+            }
+            other => panic!("expected a scan request, got {:?}", other),
+        }
+
+        let scan_rsp = AdvPdu::ScanRsp {
+            adv_addr,
+            scan_data: &[1, 2, 3],
+        };
+        match scanner.receive(&scan_rsp).unwrap() {
+            ScanEvent::ScanResponse(data) => assert_eq!(data, &[1, 2, 3]),
+            other => panic!("expected a scan response, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn scanner_ignores_advertisements_from_other_devices() {
+        let scanner_addr = addr(0x01, false);
+        let adv_addr = addr(0x02, true);
+        let mut scanner = Scanner::new(scanner_addr, adv_addr);
+
+        let other_adv = AdvPdu::AdvInd {
+            adv_addr: addr(0x03, true),
+            adv_data: &[],
+        };
+        match scanner.receive(&other_adv).unwrap() {
+            ScanEvent::Ignored => {}
+            other => panic!("expected Ignored, got {:?}", other),
+        }
     }
 }